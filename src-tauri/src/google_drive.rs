@@ -1,5 +1,8 @@
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+use tauri::Emitter;
 
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
 const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
@@ -8,6 +11,14 @@ const GOOGLE_DRIVE_API: &str = "https://www.googleapis.com/drive/v3";
 // Scopes for Google Drive access
 const DRIVE_SCOPES: &str = "https://www.googleapis.com/auth/drive.file https://www.googleapis.com/auth/drive.readonly";
 
+// Refresh the access token this many seconds before it actually expires, so a request
+// in flight doesn't race the expiry.
+const TOKEN_REFRESH_MARGIN_SECS: u64 = 60;
+
+// Resumable uploads must be chunked in multiples of 256 KiB; 8 MiB keeps request counts
+// reasonable for large discovery bundles without holding too much in memory at once.
+const RESUMABLE_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DriveFile {
     pub id: String,
@@ -38,18 +49,154 @@ struct TokenResponse {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GoogleDriveCredentials {
     pub client_id: String,
-    pub client_secret: String,
+    // Confidential-client flows still need this, but PKCE lets a distributed desktop app
+    // authorize without embedding a long-lived secret.
+    pub client_secret: Option<String>,
     pub access_token: Option<String>,
     pub refresh_token: Option<String>,
     pub expires_at: Option<u64>,
 }
 
-/// Generates the OAuth URL for Google Drive authorization
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListFilesResult {
+    pub response: DriveFilesResponse,
+    pub credentials: GoogleDriveCredentials,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DownloadResult {
+    pub path: String,
+    pub credentials: GoogleDriveCredentials,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadResult {
+    pub file: DriveFile,
+    pub credentials: GoogleDriveCredentials,
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Exchanges `refresh_token` for a fresh access token, preserving the refresh token
+/// Google doesn't bother re-sending.
+async fn refresh_credentials(
+    credentials: &GoogleDriveCredentials,
+) -> Result<GoogleDriveCredentials, String> {
+    let refresh_token = credentials
+        .refresh_token
+        .as_deref()
+        .ok_or_else(|| "No refresh token available".to_string())?;
+
+    let client = reqwest::Client::new();
+
+    let mut params = HashMap::new();
+    params.insert("client_id", credentials.client_id.as_str());
+    if let Some(client_secret) = credentials.client_secret.as_deref() {
+        params.insert("client_secret", client_secret);
+    }
+    params.insert("refresh_token", refresh_token);
+    params.insert("grant_type", "refresh_token");
+
+    let response = client
+        .post(GOOGLE_TOKEN_URL)
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to refresh token: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Token refresh failed: {}", error_text));
+    }
+
+    let token_response: TokenResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse token response: {}", e))?;
+
+    let expires_at = unix_now() + token_response.expires_in;
+
+    Ok(GoogleDriveCredentials {
+        client_id: credentials.client_id.clone(),
+        client_secret: credentials.client_secret.clone(),
+        access_token: Some(token_response.access_token),
+        refresh_token: token_response
+            .refresh_token
+            .or_else(|| credentials.refresh_token.clone()),
+        expires_at: Some(expires_at),
+    })
+}
+
+/// Returns `credentials` unchanged if the access token is still valid for more than
+/// `TOKEN_REFRESH_MARGIN_SECS`, otherwise refreshes it first.
+async fn ensure_fresh_credentials(
+    credentials: GoogleDriveCredentials,
+) -> Result<GoogleDriveCredentials, String> {
+    let needs_refresh = match (&credentials.access_token, credentials.expires_at) {
+        (Some(_), Some(expires_at)) => unix_now() + TOKEN_REFRESH_MARGIN_SECS >= expires_at,
+        _ => true,
+    };
+
+    if needs_refresh {
+        refresh_credentials(&credentials).await
+    } else {
+        Ok(credentials)
+    }
+}
+
+/// Refreshes `credentials` if the access token is within `TOKEN_REFRESH_MARGIN_SECS` of
+/// expiring, returning the (possibly updated) `GoogleDriveCredentials`.
+#[tauri::command]
+pub async fn google_drive_refresh_token(
+    credentials: GoogleDriveCredentials,
+) -> Result<GoogleDriveCredentials, String> {
+    ensure_fresh_credentials(credentials).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuthUrlResult {
+    pub url: String,
+    pub code_verifier: String,
+}
+
+/// Generates a PKCE code verifier: a cryptographically random 43-128 character string
+/// drawn from the unreserved character set (RFC 7636 section 4.1).
+fn generate_code_verifier() -> String {
+    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+    const LENGTH: usize = 64;
+
+    let mut rng = rand::thread_rng();
+    (0..LENGTH)
+        .map(|_| CHARSET[rand::Rng::gen_range(&mut rng, 0..CHARSET.len())] as char)
+        .collect()
+}
+
+/// Computes the S256 PKCE code challenge for a verifier: base64url(sha256(verifier)),
+/// no padding.
+fn code_challenge_s256(code_verifier: &str) -> String {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(code_verifier.as_bytes());
+    URL_SAFE_NO_PAD.encode(digest)
+}
+
+/// Generates the OAuth URL for Google Drive authorization, with a PKCE challenge so the
+/// app doesn't need to embed a long-lived client secret. Returns the code verifier
+/// alongside the URL so the caller can pass it back to `google_drive_callback`.
 #[tauri::command]
 pub async fn google_drive_auth(
     client_id: String,
     redirect_uri: String,
-) -> Result<String, String> {
+) -> Result<AuthUrlResult, String> {
+    let code_verifier = generate_code_verifier();
+    let code_challenge = code_challenge_s256(&code_verifier);
+
     let params = [
         ("client_id", client_id.as_str()),
         ("redirect_uri", redirect_uri.as_str()),
@@ -57,6 +204,8 @@ pub async fn google_drive_auth(
         ("scope", DRIVE_SCOPES),
         ("access_type", "offline"),
         ("prompt", "consent"),
+        ("code_challenge", code_challenge.as_str()),
+        ("code_challenge_method", "S256"),
     ];
 
     let query = params
@@ -65,25 +214,35 @@ pub async fn google_drive_auth(
         .collect::<Vec<_>>()
         .join("&");
 
-    Ok(format!("{}?{}", GOOGLE_AUTH_URL, query))
+    Ok(AuthUrlResult {
+        url: format!("{}?{}", GOOGLE_AUTH_URL, query),
+        code_verifier,
+    })
 }
 
-/// Exchanges the authorization code for tokens
+/// Exchanges the authorization code for tokens. `client_secret` may be omitted for the
+/// PKCE installed-app flow; `code_verifier` is required in that case.
 #[tauri::command]
 pub async fn google_drive_callback(
     code: String,
     client_id: String,
-    client_secret: String,
+    client_secret: Option<String>,
     redirect_uri: String,
+    code_verifier: Option<String>,
 ) -> Result<GoogleDriveCredentials, String> {
     let client = reqwest::Client::new();
 
     let mut params = HashMap::new();
     params.insert("code", code.as_str());
     params.insert("client_id", client_id.as_str());
-    params.insert("client_secret", client_secret.as_str());
+    if let Some(client_secret) = client_secret.as_deref() {
+        params.insert("client_secret", client_secret);
+    }
     params.insert("redirect_uri", redirect_uri.as_str());
     params.insert("grant_type", "authorization_code");
+    if let Some(code_verifier) = code_verifier.as_deref() {
+        params.insert("code_verifier", code_verifier);
+    }
 
     let response = client
         .post(GOOGLE_TOKEN_URL)
@@ -120,17 +279,17 @@ pub async fn google_drive_callback(
 /// Lists files from Google Drive
 #[tauri::command]
 pub async fn google_drive_list_files(
-    access_token: String,
+    credentials: GoogleDriveCredentials,
     folder_id: Option<String>,
     page_token: Option<String>,
-) -> Result<DriveFilesResponse, String> {
-    let client = reqwest::Client::new();
+) -> Result<ListFilesResult, String> {
+    let mut credentials = ensure_fresh_credentials(credentials).await?;
 
     let mut query_parts = vec![
         "trashed=false".to_string(),
     ];
 
-    if let Some(fid) = folder_id {
+    if let Some(fid) = &folder_id {
         query_parts.push(format!("'{}' in parents", fid));
     }
 
@@ -142,48 +301,225 @@ pub async fn google_drive_list_files(
         urlencoding::encode(&query)
     );
 
-    if let Some(pt) = page_token {
+    if let Some(pt) = &page_token {
         url.push_str(&format!("&pageToken={}", pt));
     }
 
-    let response = client
+    let client = reqwest::Client::new();
+    let mut response = client
         .get(&url)
-        .header("Authorization", format!("Bearer {}", access_token))
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
         .send()
         .await
         .map_err(|e| format!("Failed to list files: {}", e))?;
 
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        credentials = refresh_credentials(&credentials).await?;
+        response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list files: {}", e))?;
+    }
+
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
         return Err(format!("Failed to list files: {}", error_text));
     }
 
-    response
+    let files_response = response
         .json::<DriveFilesResponse>()
         .await
-        .map_err(|e| format!("Failed to parse files response: {}", e))
+        .map_err(|e| format!("Failed to parse files response: {}", e))?;
+
+    Ok(ListFilesResult {
+        response: files_response,
+        credentials,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DriveSearchFilter {
+    pub name_contains: Option<String>,
+    pub mime_type: Option<String>,
+    /// Friendly type name ("pdf", "doc", "folder", ...), mapped to its real mimeType.
+    /// Takes precedence over `mime_type` when both are set.
+    pub file_type: Option<String>,
+    pub full_text_contains: Option<String>,
+    pub modified_after: Option<String>,
+    pub starred: Option<bool>,
+    pub parent_id: Option<String>,
+}
+
+/// Maps a friendly type name to its Drive mimeType, if recognized.
+fn mime_type_for_friendly_name(name: &str) -> Option<&'static str> {
+    match name.to_lowercase().as_str() {
+        "pdf" => Some("application/pdf"),
+        "doc" | "docx" | "word" => {
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document")
+        }
+        "sheet" | "spreadsheet" | "xlsx" => Some(
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        ),
+        "slides" | "presentation" | "pptx" => Some(
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        ),
+        "folder" => Some("application/vnd.google-apps.folder"),
+        "doc-google" | "gdoc" => Some("application/vnd.google-apps.document"),
+        "sheet-google" | "gsheet" => Some("application/vnd.google-apps.spreadsheet"),
+        "slides-google" | "gslides" => Some("application/vnd.google-apps.presentation"),
+        "image" => Some("image/*"),
+        "text" | "txt" => Some("text/plain"),
+        _ => None,
+    }
+}
+
+/// Escapes single quotes in a value destined for a Drive `q` string literal, per Drive's
+/// search query syntax (a literal `'` is escaped as `\'`).
+fn escape_query_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Builds the Drive `q` string for a [`DriveSearchFilter`], joining clauses with `and`.
+/// Errors rather than silently widening the search if `file_type` isn't a recognized
+/// friendly name.
+fn build_search_query(filter: &DriveSearchFilter) -> Result<String, String> {
+    let mut clauses = vec!["trashed=false".to_string()];
+
+    if let Some(name) = &filter.name_contains {
+        clauses.push(format!("name contains '{}'", escape_query_value(name)));
+    }
+
+    if let Some(file_type) = &filter.file_type {
+        let mime = mime_type_for_friendly_name(file_type)
+            .ok_or_else(|| format!("Unrecognized file_type: '{}'", file_type))?;
+        if mime.ends_with("/*") {
+            let prefix = mime.trim_end_matches('*');
+            clauses.push(format!("mimeType contains '{}'", escape_query_value(prefix)));
+        } else {
+            clauses.push(format!("mimeType='{}'", escape_query_value(mime)));
+        }
+    } else if let Some(mime_type) = &filter.mime_type {
+        clauses.push(format!("mimeType='{}'", escape_query_value(mime_type)));
+    }
+
+    if let Some(text) = &filter.full_text_contains {
+        clauses.push(format!("fullText contains '{}'", escape_query_value(text)));
+    }
+
+    if let Some(modified_after) = &filter.modified_after {
+        clauses.push(format!(
+            "modifiedTime > '{}'",
+            escape_query_value(modified_after)
+        ));
+    }
+
+    if let Some(starred) = filter.starred {
+        clauses.push(format!("starred={}", starred));
+    }
+
+    if let Some(parent_id) = &filter.parent_id {
+        clauses.push(format!("'{}' in parents", escape_query_value(parent_id)));
+    }
+
+    Ok(clauses.join(" and "))
 }
 
-/// Downloads a file from Google Drive
+/// Searches Google Drive with a structured filter (name, type, full-text, modified-after,
+/// starred, parent), rather than the folder-only listing `google_drive_list_files` offers.
+#[tauri::command]
+pub async fn google_drive_search(
+    credentials: GoogleDriveCredentials,
+    filter: DriveSearchFilter,
+    page_token: Option<String>,
+    order_by: Option<String>,
+) -> Result<ListFilesResult, String> {
+    let mut credentials = ensure_fresh_credentials(credentials).await?;
+
+    let query = build_search_query(&filter)?;
+
+    let mut url = format!(
+        "{}/files?q={}&fields=files(id,name,mimeType,modifiedTime,size,parents),nextPageToken&pageSize=100",
+        GOOGLE_DRIVE_API,
+        urlencoding::encode(&query)
+    );
+
+    if let Some(pt) = &page_token {
+        url.push_str(&format!("&pageToken={}", pt));
+    }
+
+    if let Some(order_by) = &order_by {
+        url.push_str(&format!("&orderBy={}", urlencoding::encode(order_by)));
+    }
+
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to search files: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        credentials = refresh_credentials(&credentials).await?;
+        response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to search files: {}", e))?;
+    }
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to search files: {}", error_text));
+    }
+
+    let files_response = response
+        .json::<DriveFilesResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse files response: {}", e))?;
+
+    Ok(ListFilesResult {
+        response: files_response,
+        credentials,
+    })
+}
+
+/// Downloads a file from Google Drive, streaming it to disk so multi-hundred-megabyte
+/// exports don't have to be buffered in memory first.
 #[tauri::command]
 pub async fn google_drive_download(
-    access_token: String,
+    app_handle: tauri::AppHandle,
+    credentials: GoogleDriveCredentials,
     file_id: String,
     file_name: String,
     download_path: String,
-) -> Result<String, String> {
+) -> Result<DownloadResult, String> {
+    let mut credentials = ensure_fresh_credentials(credentials).await?;
     let client = reqwest::Client::new();
 
     // First, get file metadata to check if it's a Google Docs file
     let metadata_url = format!("{}/files/{}?fields=mimeType", GOOGLE_DRIVE_API, file_id);
 
-    let metadata_response = client
+    let mut metadata_response = client
         .get(&metadata_url)
-        .header("Authorization", format!("Bearer {}", access_token))
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
         .send()
         .await
         .map_err(|e| format!("Failed to get file metadata: {}", e))?;
 
+    if metadata_response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        credentials = refresh_credentials(&credentials).await?;
+        metadata_response = client
+            .get(&metadata_url)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+    }
+
     if !metadata_response.status().is_success() {
         let error_text = metadata_response.text().await.unwrap_or_default();
         return Err(format!("Failed to get metadata: {}", error_text));
@@ -217,22 +553,31 @@ pub async fn google_drive_download(
         )
     };
 
-    let response = client
+    let mut response = client
         .get(&download_url)
-        .header("Authorization", format!("Bearer {}", access_token))
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
         .send()
         .await
         .map_err(|e| format!("Failed to download file: {}", e))?;
 
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        credentials = refresh_credentials(&credentials).await?;
+        response = client
+            .get(&download_url)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download file: {}", e))?;
+    }
+
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
         return Err(format!("Failed to download: {}", error_text));
     }
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read file bytes: {}", e))?;
+    let total_bytes = response
+        .content_length()
+        .unwrap_or(0);
 
     // Construct final path
     let final_name = if final_extension.is_empty() {
@@ -243,20 +588,41 @@ pub async fn google_drive_download(
 
     let final_path = std::path::Path::new(&download_path).join(&final_name);
 
-    std::fs::write(&final_path, bytes)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+    let mut file = std::fs::File::create(&final_path)
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+
+    let mut bytes_written: u64 = 0;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to read file bytes: {}", e))?;
+
+        file.write_all(&chunk)
+            .map_err(|e| format!("Failed to write file: {}", e))?;
 
-    Ok(final_path.to_string_lossy().to_string())
+        bytes_written += chunk.len() as u64;
+
+        let _ = app_handle.emit(
+            "download-progress",
+            DownloadProgressEvent { bytes_written, total_bytes },
+        );
+    }
+
+    Ok(DownloadResult {
+        path: final_path.to_string_lossy().to_string(),
+        credentials,
+    })
 }
 
 /// Uploads a file to Google Drive
 #[tauri::command]
 pub async fn google_drive_upload(
-    access_token: String,
+    credentials: GoogleDriveCredentials,
     local_path: String,
     folder_id: Option<String>,
     file_name: Option<String>,
-) -> Result<DriveFile, String> {
+) -> Result<UploadResult, String> {
+    let mut credentials = ensure_fresh_credentials(credentials).await?;
     let client = reqwest::Client::new();
 
     let path = std::path::Path::new(&local_path);
@@ -307,32 +673,418 @@ pub async fn google_drive_upload(
     // End boundary
     body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
 
-    let response = client
-        .post("https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart&fields=id,name,mimeType,modifiedTime,size,parents")
-        .header("Authorization", format!("Bearer {}", access_token))
+    let upload_url = "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart&fields=id,name,mimeType,modifiedTime,size,parents";
+
+    let mut response = client
+        .post(upload_url)
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
         .header("Content-Type", format!("multipart/related; boundary={}", boundary))
-        .body(body)
+        .body(body.clone())
         .send()
         .await
         .map_err(|e| format!("Failed to upload file: {}", e))?;
 
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        credentials = refresh_credentials(&credentials).await?;
+        response = client
+            .post(upload_url)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .header("Content-Type", format!("multipart/related; boundary={}", boundary))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to upload file: {}", e))?;
+    }
+
     if !response.status().is_success() {
         let error_text = response.text().await.unwrap_or_default();
         return Err(format!("Failed to upload: {}", error_text));
     }
 
-    response
+    let file = response
         .json::<DriveFile>()
         .await
-        .map_err(|e| format!("Failed to parse upload response: {}", e))
+        .map_err(|e| format!("Failed to parse upload response: {}", e))?;
+
+    Ok(UploadResult { file, credentials })
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct UploadProgressEvent {
+    bytes_sent: u64,
+    total_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgressEvent {
+    bytes_written: u64,
+    total_bytes: u64,
+}
+
+/// Uploads a file to Google Drive using the resumable protocol, suitable for large
+/// discovery bundles or scanned case files. Emits an `upload-progress` event after every
+/// chunk so the frontend can show a progress bar. `folder_id` and `file_name` only take
+/// effect when starting a fresh session (`session_uri` is `None`) — resuming an existing
+/// session reuses the metadata it was created with.
+#[tauri::command]
+pub async fn google_drive_upload_resumable(
+    app_handle: tauri::AppHandle,
+    credentials: GoogleDriveCredentials,
+    local_path: String,
+    folder_id: Option<String>,
+    file_name: Option<String>,
+    session_uri: Option<String>,
+) -> Result<UploadResult, String> {
+    let credentials = ensure_fresh_credentials(credentials).await?;
+    let client = reqwest::Client::new();
+
+    let path = std::path::Path::new(&local_path);
+    let name = file_name.unwrap_or_else(|| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "untitled".to_string())
+    });
+
+    let total_bytes = std::fs::metadata(&local_path)
+        .map_err(|e| format!("Failed to read local file metadata: {}", e))?
+        .len();
+
+    // Start (or resume) the resumable session.
+    let session_uri = match session_uri {
+        Some(uri) => uri,
+        None => {
+            let mut metadata = serde_json::json!({ "name": name });
+            if let Some(fid) = folder_id {
+                metadata["parents"] = serde_json::json!([fid]);
+            }
+
+            let response = client
+                .post("https://www.googleapis.com/upload/drive/v3/files?uploadType=resumable")
+                .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+                .header("Content-Type", "application/json; charset=UTF-8")
+                .json(&metadata)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to start resumable upload: {}", e))?;
+
+            if !response.status().is_success() {
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(format!("Failed to start resumable upload: {}", error_text));
+            }
+
+            response
+                .headers()
+                .get("Location")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+                .ok_or_else(|| "Resumable session did not return a Location header".to_string())?
+        }
+    };
+
+    // A 0-byte file has no chunk to stream: the Drive resumable protocol finalizes it
+    // with a single zero-length PUT, and the general chunk loop below (which assumes at
+    // least one byte remaining) doesn't apply.
+    if total_bytes == 0 {
+        let response = client
+            .put(&session_uri)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .header("Content-Length", "0")
+            .header("Content-Range", "bytes */0")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to finalize empty upload: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("Failed to finalize empty upload: {}", error_text));
+        }
+
+        let file = response
+            .json::<DriveFile>()
+            .await
+            .map_err(|e| format!("Failed to parse upload response: {}", e))?;
+
+        let _ = app_handle.emit(
+            "upload-progress",
+            UploadProgressEvent { bytes_sent: 0, total_bytes: 0 },
+        );
+
+        return Ok(UploadResult { file, credentials });
+    }
+
+    let mut file = std::fs::File::open(&local_path)
+        .map_err(|e| format!("Failed to open local file: {}", e))?;
+
+    // Ask Drive where to resume from in case this session URI already has bytes uploaded.
+    let mut bytes_sent = match resumable_upload_offset(&client, &credentials, &session_uri, total_bytes).await? {
+        ResumableOffset::Complete(file) => return Ok(UploadResult { file, credentials }),
+        ResumableOffset::Incomplete(offset) => offset,
+    };
+
+    if bytes_sent > total_bytes {
+        return Err(format!(
+            "Resumable session reported an offset ({}) past the end of the file ({} bytes); the session is stale or invalid",
+            bytes_sent, total_bytes
+        ));
+    }
+
+    loop {
+        file.seek(SeekFrom::Start(bytes_sent))
+            .map_err(|e| format!("Failed to seek local file: {}", e))?;
+
+        // `Read::read` may return fewer bytes than the buffer even mid-file, which would
+        // produce a chunk length that isn't a multiple of 256 KiB and gets rejected by
+        // Drive. Size the chunk up front and fill it exactly instead.
+        let chunk_len = std::cmp::min(RESUMABLE_CHUNK_SIZE as u64, total_bytes.saturating_sub(bytes_sent)) as usize;
+        let mut buffer = vec![0u8; chunk_len];
+        file.read_exact(&mut buffer)
+            .map_err(|e| format!("Failed to read local file: {}", e))?;
+
+        let chunk_start = bytes_sent;
+        let chunk_end = bytes_sent + chunk_len as u64 - 1;
+
+        let response = client
+            .put(&session_uri)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .header("Content-Length", chunk_len.to_string())
+            .header(
+                "Content-Range",
+                format!("bytes {}-{}/{}", chunk_start, chunk_end, total_bytes),
+            )
+            .body(buffer)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to upload chunk: {}", e))?;
+
+        let status = response.status();
+
+        if status.as_u16() == 308 {
+            bytes_sent = response
+                .headers()
+                .get("Range")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|range| range.rsplit('-').next())
+                .and_then(|n| n.parse::<u64>().ok())
+                .map(|n| n + 1)
+                .unwrap_or(chunk_end + 1);
+
+            if bytes_sent > total_bytes {
+                return Err(format!(
+                    "Resumable session reported an offset ({}) past the end of the file ({} bytes); the session is stale or invalid",
+                    bytes_sent, total_bytes
+                ));
+            }
+
+            let _ = app_handle.emit(
+                "upload-progress",
+                UploadProgressEvent { bytes_sent, total_bytes },
+            );
+            continue;
+        }
+
+        if status.is_success() {
+            let _ = app_handle.emit(
+                "upload-progress",
+                UploadProgressEvent { bytes_sent: total_bytes, total_bytes },
+            );
+
+            let file = response
+                .json::<DriveFile>()
+                .await
+                .map_err(|e| format!("Failed to parse upload response: {}", e))?;
+
+            return Ok(UploadResult { file, credentials });
+        }
+
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "Resumable upload failed (session: {}): {}",
+            session_uri, error_text
+        ));
+    }
+}
+
+/// Result of probing a resumable session's status: either the byte offset to resume
+/// from, or the finalized file if the session had already fully completed.
+enum ResumableOffset {
+    Incomplete(u64),
+    Complete(DriveFile),
+}
+
+/// Queries how many bytes of a resumable session Drive has already accepted, so an
+/// interrupted upload can resume instead of restarting from zero. A session the server
+/// already finished (e.g. the caller's last attempt completed but never saw the
+/// response) resolves as `Complete` rather than being re-uploaded.
+async fn resumable_upload_offset(
+    client: &reqwest::Client,
+    credentials: &GoogleDriveCredentials,
+    session_uri: &str,
+    total_bytes: u64,
+) -> Result<ResumableOffset, String> {
+    let response = client
+        .put(session_uri)
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+        .header("Content-Length", "0")
+        .header("Content-Range", format!("bytes */{}", total_bytes))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query upload offset: {}", e))?;
+
+    if response.status().as_u16() == 308 {
+        let offset = response
+            .headers()
+            .get("Range")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|range| range.rsplit('-').next())
+            .and_then(|n| n.parse::<u64>().ok())
+            .map(|n| n + 1)
+            .unwrap_or(0);
+        Ok(ResumableOffset::Incomplete(offset))
+    } else if response.status().is_success() {
+        let file = response
+            .json::<DriveFile>()
+            .await
+            .map_err(|e| format!("Failed to parse finalized upload response: {}", e))?;
+        Ok(ResumableOffset::Complete(file))
+    } else {
+        // Not one of the documented shapes (308 with/without Range, or a completed
+        // 2xx) — the session URI is expired, invalid, or the probe otherwise failed.
+        // Report it instead of treating it as "fresh session, restart from byte 0",
+        // which would silently upload every chunk to a dead session.
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        Err(format!(
+            "Failed to query upload status ({}): {}",
+            status, error_text
+        ))
+    }
 }
 
-/// Disconnects Google Drive (clears stored credentials)
+/// Disconnects Google Drive by revoking `token` (an access or refresh token) so it can no
+/// longer be used on Google's side. The frontend still clears the stored credentials via
+/// tauri-plugin-store; this is what makes "disconnect" actually sever access.
 #[tauri::command]
-pub async fn google_drive_disconnect() -> Result<(), String> {
-    // The actual credential clearing happens on the frontend side via tauri-plugin-store
-    // This command can be used to revoke the token if needed
-    Ok(())
+pub async fn google_drive_disconnect(token: String) -> Result<(), String> {
+    let client = reqwest::Client::new();
+
+    let mut params = HashMap::new();
+    params.insert("token", token.as_str());
+
+    let response = client
+        .post("https://oauth2.googleapis.com/revoke")
+        .form(&params)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to revoke token: {}", e))?;
+
+    // Google returns 200 for a successful revocation and 400 with `invalid_token` for a
+    // token that's already invalid or expired — both mean access is no longer granted.
+    if response.status().is_success() {
+        return Ok(());
+    }
+
+    let status = response.status();
+    let error_text = response.text().await.unwrap_or_default();
+
+    if status == reqwest::StatusCode::BAD_REQUEST && error_text.contains("invalid_token") {
+        return Ok(());
+    }
+
+    Err(format!("Failed to revoke token: {}", error_text))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DriveChange {
+    #[serde(rename = "fileId")]
+    pub file_id: String,
+    pub file: Option<DriveFile>,
+    pub removed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DriveChangesResponse {
+    pub changes: Vec<DriveChange>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+    #[serde(rename = "newStartPageToken")]
+    pub new_start_page_token: Option<String>,
+}
+
+/// Fetches the opaque page token to start tracking changes from. Persist the result and
+/// pass it to `google_drive_list_changes` to begin an incremental sync.
+#[tauri::command]
+pub async fn google_drive_get_start_page_token(
+    credentials: GoogleDriveCredentials,
+) -> Result<String, String> {
+    let credentials = ensure_fresh_credentials(credentials).await?;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("{}/changes/startPageToken", GOOGLE_DRIVE_API))
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get start page token: {}", e))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to get start page token: {}", error_text));
+    }
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse start page token response: {}", e))?;
+
+    body["startPageToken"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Response did not include a startPageToken".to_string())
+}
+
+/// Lists the files added, modified, or removed since `page_token`, for an incremental
+/// sync of a local case folder against Drive. Store `new_start_page_token` for the next
+/// poll once the caller has fully processed the returned changes.
+#[tauri::command]
+pub async fn google_drive_list_changes(
+    credentials: GoogleDriveCredentials,
+    page_token: String,
+) -> Result<DriveChangesResponse, String> {
+    let mut credentials = ensure_fresh_credentials(credentials).await?;
+
+    let url = format!(
+        "{}/changes?pageToken={}&fields=changes(fileId,file(id,name,mimeType,modifiedTime,size,parents),removed),nextPageToken,newStartPageToken",
+        GOOGLE_DRIVE_API,
+        urlencoding::encode(&page_token)
+    );
+
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to list changes: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        credentials = refresh_credentials(&credentials).await?;
+        response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", credentials.access_token.clone().unwrap_or_default()))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to list changes: {}", e))?;
+    }
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("Failed to list changes: {}", error_text));
+    }
+
+    response
+        .json::<DriveChangesResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse changes response: {}", e))
 }
 
 // URL encoding helper
@@ -2,7 +2,9 @@ mod google_drive;
 
 use google_drive::{
     google_drive_auth, google_drive_callback, google_drive_list_files,
-    google_drive_download, google_drive_upload, google_drive_disconnect
+    google_drive_download, google_drive_upload, google_drive_disconnect,
+    google_drive_refresh_token, google_drive_upload_resumable, google_drive_search,
+    google_drive_get_start_page_token, google_drive_list_changes
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -21,7 +23,12 @@ pub fn run() {
             google_drive_list_files,
             google_drive_download,
             google_drive_upload,
-            google_drive_disconnect
+            google_drive_disconnect,
+            google_drive_refresh_token,
+            google_drive_upload_resumable,
+            google_drive_search,
+            google_drive_get_start_page_token,
+            google_drive_list_changes
         ])
         .setup(|app| {
             if cfg!(debug_assertions) {